@@ -0,0 +1,149 @@
+use clap::{App, AppSettings, Arg, ArgGroup, ArgMatches};
+
+/// Build the command-line interface definition and parse the arguments passed to newdoc.
+pub fn get_args() -> ArgMatches<'static> {
+    App::new("newdoc")
+        .version(env!("CARGO_PKG_VERSION"))
+        .about("Generate pre-populated AsciiDoc modules and assemblies for Red Hat and Fedora documentation")
+        .setting(AppSettings::ArgRequiredElseHelp)
+        .arg(
+            Arg::with_name("assembly")
+                .long("assembly")
+                .short("a")
+                .value_name("TITLE")
+                .multiple(true)
+                .number_of_values(1)
+                .help("Generate an assembly module with this title"),
+        )
+        .arg(
+            Arg::with_name("concept")
+                .long("concept")
+                .short("c")
+                .value_name("TITLE")
+                .multiple(true)
+                .number_of_values(1)
+                .help("Generate a concept module with this title"),
+        )
+        .arg(
+            Arg::with_name("procedure")
+                .long("procedure")
+                .short("p")
+                .value_name("TITLE")
+                .multiple(true)
+                .number_of_values(1)
+                .help("Generate a procedure module with this title"),
+        )
+        .arg(
+            Arg::with_name("reference")
+                .long("reference")
+                .short("r")
+                .value_name("TITLE")
+                .multiple(true)
+                .number_of_values(1)
+                .help("Generate a reference module with this title"),
+        )
+        .arg(
+            Arg::with_name("snippet")
+                .long("snippet")
+                .short("s")
+                .value_name("TITLE")
+                .multiple(true)
+                .number_of_values(1)
+                .help("Generate a snippet module with this title"),
+        )
+        .group(
+            ArgGroup::with_name("module-type")
+                .args(&["assembly", "concept", "procedure", "reference", "snippet"])
+                .multiple(true)
+                .required(true),
+        )
+        .arg(
+            Arg::with_name("include-in")
+                .long("include-in")
+                .value_name("TITLE")
+                .help("Generate a populated assembly that includes the other generated modules"),
+        )
+        .arg(
+            Arg::with_name("validate")
+                .long("validate")
+                .value_name("FILE")
+                .multiple(true)
+                .number_of_values(1)
+                .help("Validate the given AsciiDoc file or files after generating the requested modules"),
+        )
+        .arg(
+            Arg::with_name("no-comments")
+                .long("no-comments")
+                .help("Do not include explanatory comments in the generated files"),
+        )
+        .arg(
+            Arg::with_name("no-prefixes")
+                .long("no-prefixes")
+                .help("Do not prefix the generated IDs and file names with the module type"),
+        )
+        .arg(
+            Arg::with_name("no-examples")
+                .long("no-examples")
+                .help("Do not include example content in the generated files"),
+        )
+        .arg(
+            Arg::with_name("target-dir")
+                .long("target-dir")
+                .short("T")
+                .value_name("DIRECTORY")
+                .help("Save the generated files to this directory instead of the current one"),
+        )
+        .arg(
+            Arg::with_name("stdout")
+                .long("stdout")
+                .conflicts_with("check")
+                .help("Print the generated content to the standard output instead of writing files"),
+        )
+        .arg(
+            Arg::with_name("check")
+                .long("check")
+                .conflicts_with("stdout")
+                .help("Check whether the target files would change, without writing anything"),
+        )
+        .arg(
+            Arg::with_name("output-format")
+                .long("output-format")
+                .value_name("FORMAT")
+                .possible_values(&["text", "json", "github-actions"])
+                .default_value("text")
+                .help("Choose how --validate reports its findings"),
+        )
+        .arg(
+            Arg::with_name("fail-fast")
+                .long("fail-fast")
+                .requires("validate")
+                .help("Abort --validate on the first file that fails instead of checking them all"),
+        )
+        .arg(
+            Arg::with_name("fix")
+                .long("fix")
+                .requires("validate")
+                .help("Automatically repair the issues --validate can fix mechanically"),
+        )
+        .arg(
+            Arg::with_name("diff")
+                .long("diff")
+                .requires("fix")
+                .help("With --fix, print the proposed changes instead of writing them"),
+        )
+        .arg(
+            Arg::with_name("verbose")
+                .long("verbose")
+                .short("v")
+                .conflicts_with("quiet")
+                .help("Print more detailed progress information"),
+        )
+        .arg(
+            Arg::with_name("quiet")
+                .long("quiet")
+                .short("q")
+                .conflicts_with("verbose")
+                .help("Print only warnings and errors"),
+        )
+        .get_matches()
+}
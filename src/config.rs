@@ -0,0 +1,156 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// An optional per-project configuration file, typically named `.newdoc.toml`.
+///
+/// Every field mirrors an option in [`Options`](crate::Options) but is wrapped in `Option`
+/// so that an absent key simply means "no opinion", letting the file sit between the
+/// command-line flags and the built-in defaults in the precedence chain.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct Config {
+    pub comments: Option<bool>,
+    pub prefixes: Option<bool>,
+    pub examples: Option<bool>,
+    pub target_dir: Option<String>,
+    /// Custom title prefixes per module type (for example `concept = "Concept: "`). The value
+    /// is slugified the same way a title is before it ends up in a generated ID or file name,
+    /// so it doesn't need to be identifier-safe itself.
+    /// This has no command-line equivalent and is only ever set via the config file.
+    #[serde(default)]
+    pub title_prefixes: HashMap<String, String>,
+}
+
+impl Config {
+    /// Look for `.newdoc.toml` starting in `target_dir` and walking up its ancestors.
+    /// If none is found, fall back to `newdoc/config.toml` in the user's config directory.
+    /// Returns the default (empty) configuration if no file is found anywhere.
+    pub fn load(target_dir: &str) -> Self {
+        let path = Self::find_project_config(target_dir).or_else(Self::find_user_config);
+
+        match path {
+            Some(path) => Self::read(&path).unwrap_or_else(|error| {
+                log::warn!(
+                    "Failed to read the configuration file {:?}: {}",
+                    path,
+                    error
+                );
+                Self::default()
+            }),
+            None => Self::default(),
+        }
+    }
+
+    fn find_project_config(target_dir: &str) -> Option<PathBuf> {
+        let mut dir = fs::canonicalize(target_dir).ok()?;
+
+        loop {
+            let candidate = dir.join(".newdoc.toml");
+
+            if candidate.is_file() {
+                return Some(candidate);
+            }
+
+            if !dir.pop() {
+                return None;
+            }
+        }
+    }
+
+    fn find_user_config() -> Option<PathBuf> {
+        let candidate = dirs::config_dir()?.join("newdoc").join("config.toml");
+
+        candidate.is_file().then_some(candidate)
+    }
+
+    fn read(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let content = fs::read_to_string(path)?;
+        let config: Self = toml::from_str(&content)?;
+
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    /// Create a fresh, empty directory under the system temp directory, unique to this test run.
+    fn temp_dir(label: &str) -> PathBuf {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let dir = std::env::temp_dir().join(format!("newdoc-config-test-{}-{}", label, nanos));
+
+        fs::create_dir_all(&dir).unwrap();
+
+        dir
+    }
+
+    #[test]
+    fn find_project_config_finds_the_file_in_target_dir_itself() {
+        let dir = temp_dir("direct");
+        fs::write(dir.join(".newdoc.toml"), "").unwrap();
+
+        assert_eq!(
+            Config::find_project_config(dir.to_str().unwrap()),
+            Some(dir.join(".newdoc.toml"))
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn find_project_config_walks_up_to_an_ancestor() {
+        let dir = temp_dir("ancestor");
+        let nested = dir.join("a").join("b");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(dir.join(".newdoc.toml"), "").unwrap();
+
+        assert_eq!(
+            Config::find_project_config(nested.to_str().unwrap()),
+            Some(dir.join(".newdoc.toml"))
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn find_project_config_returns_none_without_a_config_file() {
+        let dir = temp_dir("missing");
+
+        assert_eq!(Config::find_project_config(dir.to_str().unwrap()), None);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn read_parses_a_valid_config_file() {
+        let dir = temp_dir("read");
+        let path = dir.join(".newdoc.toml");
+        fs::write(&path, "comments = false\ntarget-dir = \"out\"\n").unwrap();
+
+        let config = Config::read(&path).unwrap();
+
+        assert_eq!(config.comments, Some(false));
+        assert_eq!(config.target_dir, Some(String::from("out")));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn load_falls_back_to_the_default_config_when_nothing_is_found() {
+        let dir = temp_dir("load-default");
+
+        let config = Config::load(dir.to_str().unwrap());
+
+        assert_eq!(config.comments, None);
+        assert!(config.title_prefixes.is_empty());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
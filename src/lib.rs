@@ -1,14 +1,18 @@
 use clap::ArgMatches;
 use color_eyre::eyre::Result;
+use std::collections::HashMap;
 
 pub mod cmd_line;
+mod config;
 mod logging;
 mod module;
 mod templating;
 mod validation;
 mod write;
 
+pub use config::Config;
 pub use module::{Input, Module, ModuleType};
+pub use validation::{Diagnostic, Level};
 
 /// This struct stores options based on the command-line arguments,
 /// and is passed to various functions across the program.
@@ -19,10 +23,15 @@ pub struct Options {
     pub examples: bool,
     pub target_dir: String,
     pub verbosity: Verbosity,
+    /// Title prefixes per module type, as set in the project configuration file.
+    /// There is no command-line equivalent for this option.
+    pub title_prefixes: HashMap<String, String>,
+    pub write_mode: WriteMode,
 }
 
 impl Options {
-    /// Set current options based on the command-line options
+    /// Set current options based on the command-line options, falling back to the project
+    /// configuration file and then to the built-in defaults, in that order of precedence.
     pub fn new(args: &ArgMatches) -> Self {
         // Determine the configured verbosity level.
         // The clap configuration ensures that verbose and quiet
@@ -35,24 +44,65 @@ impl Options {
             Verbosity::Default
         };
 
+        // The config file is searched for starting at the target directory, so resolve a
+        // directory to search from first, falling back to "." if the CLI didn't set one -- the
+        // CLI value itself, if present, still takes precedence over the config file below.
+        let cli_target_dir = args.value_of("target-dir").map(String::from);
+        let search_dir = cli_target_dir.clone().unwrap_or_else(|| String::from("."));
+
+        let config = Config::load(&search_dir);
+
         Self {
-            // Comments and prefixes are enabled (true) by default unless you disable them
-            // on the command line. If the no-comments or no-prefixes option is passed
-            // (occurences > 0), the feature is disabled, so the option is set to false.
-            comments: !args.is_present("no-comments"),
-            prefixes: !args.is_present("no-prefixes"),
-            examples: !args.is_present("no-examples"),
-            // Set the target directory as specified or fall back on the current directory
-            target_dir: if let Some(dir) = args.value_of("target-dir") {
-                String::from(dir)
+            // Comments, prefixes, and examples are enabled (true) by default unless disabled
+            // either on the command line or in the config file. A `no-comments`-style flag on
+            // the command line always wins, even over a `true` set in the config file, because
+            // clap can't otherwise tell "absent" from "defaulted" -- we only consult the config
+            // value when the negation flag was not explicitly passed.
+            comments: resolve_bool_flag(args.is_present("no-comments"), config.comments),
+            prefixes: resolve_bool_flag(args.is_present("no-prefixes"), config.prefixes),
+            examples: resolve_bool_flag(args.is_present("no-examples"), config.examples),
+            target_dir: resolve_target_dir(cli_target_dir, config.target_dir, search_dir),
+            verbosity,
+            title_prefixes: config.title_prefixes,
+            write_mode: if args.is_present("check") {
+                WriteMode::Check
+            } else if args.is_present("stdout") {
+                WriteMode::Stdout
             } else {
-                String::from(".")
+                WriteMode::Overwrite
             },
-            verbosity,
         }
     }
 }
 
+/// Resolve a boolean option that's true by default unless negated on the command line (in
+/// which case it wins unconditionally) or in the config file.
+fn resolve_bool_flag(negated_on_cli: bool, config_value: Option<bool>) -> bool {
+    if negated_on_cli {
+        false
+    } else {
+        config_value.unwrap_or(true)
+    }
+}
+
+/// Resolve the target directory with the precedence the config subsystem promises:
+/// explicit CLI flag > config file value > built-in default.
+fn resolve_target_dir(
+    cli_value: Option<String>,
+    config_value: Option<String>,
+    default: String,
+) -> String {
+    cli_value.unwrap_or_else(|| config_value.unwrap_or(default))
+}
+
+/// Whether a file's diagnostics include at least one `Error`-level finding, i.e. whether the
+/// file as a whole failed validation.
+fn has_error_diagnostic(diagnostics: &[Diagnostic]) -> bool {
+    diagnostics
+        .iter()
+        .any(|diagnostic| diagnostic.level == Level::Error)
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum Verbosity {
     Verbose,
@@ -60,6 +110,42 @@ pub enum Verbosity {
     Quiet,
 }
 
+/// Controls how a generated module ends up outside of newdoc, mirroring rustfmt's own
+/// `WriteMode`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WriteMode {
+    /// Write the generated content to disk, overwriting any existing file (the default).
+    Overwrite,
+    /// Print the generated content to the standard output instead of writing any files.
+    Stdout,
+    /// Generate the content in memory and fail if it differs from what is already on disk,
+    /// without writing anything.
+    Check,
+}
+
+/// How the diagnostics from `--validate` are reported to the user, mirroring ui_test's own
+/// choice between a human-readable report and CI-friendly machine output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Plain, human-readable text, one diagnostic per line.
+    Text,
+    /// A JSON array of diagnostics, for tooling that wants to consume the results.
+    Json,
+    /// GitHub Actions workflow commands (`::error file=...,line=...::message`), so each
+    /// diagnostic is surfaced as an inline CI annotation.
+    GithubActions,
+}
+
+impl OutputFormat {
+    fn from_args(args: &ArgMatches) -> Self {
+        match args.value_of("output-format") {
+            Some("json") => Self::Json,
+            Some("github-actions") => Self::GithubActions,
+            _ => Self::Text,
+        }
+    }
+}
+
 pub fn run(options: Options, cmdline_args: ArgMatches) -> Result<()> {
     // Initialize the logging system based on the set verbosity
     logging::initialize_logger(options.verbosity)?;
@@ -81,19 +167,14 @@ pub fn run(options: Options, cmdline_args: ArgMatches) -> Result<()> {
         }
     }
 
-    // Write all non-populated modules to the disk
-    for module in &non_populated {
-        module.write_file(&options)?;
-    }
-
     // Treat the populated assembly module as a special case:
     // * There can be only one populated assembly
     // * It must be generated after the other modules so that it can use their include statements
     if let Some(title) = cmdline_args.value_of("include-in") {
         // Gather all include statements for the other modules
         let include_statements: Vec<String> = non_populated
-            .into_iter()
-            .map(|module| module.include_statement)
+            .iter()
+            .map(|module| module.include_statement.clone())
             .collect();
 
         // The include_statements should never be empty thanks to the required group in clap
@@ -104,13 +185,89 @@ pub fn run(options: Options, cmdline_args: ArgMatches) -> Result<()> {
             .include(include_statements)
             .into();
 
-        populated.write_file(&options)?;
+        non_populated.push(populated);
     }
 
-    // Validate all file names specified on the command line
+    // Produce every generated module according to the configured write mode: written to disk,
+    // printed to the standard output, or merely checked against what is already on disk.
+    write::write_modules(&non_populated, &options)?;
+
+    // Validate all file names specified on the command line. By default, every file is
+    // validated even if an earlier one failed, mirroring cargo test's `no_fail_fast` semantics;
+    // pass --fail-fast to abort on the first failing file instead.
     if let Some(files_iterator) = cmdline_args.values_of("validate") {
-        for file in files_iterator {
-            validation::validate(file)?;
+        let output_format = OutputFormat::from_args(&cmdline_args);
+        let fail_fast = cmdline_args.is_present("fail-fast");
+        let fix = cmdline_args.is_present("fix");
+        // --fix --diff is a dry run: print the proposed diff without writing anything. This is
+        // deliberately its own flag rather than reusing --check: --check already controls how
+        // write_modules() handles module *generation*, and since the module-type group is
+        // required, a --validate --check invocation also runs write_modules() in Check mode
+        // first, which would bail out before the validate/fix block below ever ran.
+        let fix_dry_run = cmdline_args.is_present("diff");
+        let files: Vec<&str> = files_iterator.collect();
+        let mut diagnostics = Vec::new();
+        let mut failed_files = 0;
+        let mut fixes_applied = 0;
+        let mut fixes_remaining = 0;
+
+        for file in &files {
+            match validation::validate(file) {
+                Ok(mut file_diagnostics) => {
+                    let file_failed = has_error_diagnostic(&file_diagnostics);
+
+                    if file_failed {
+                        failed_files += 1;
+                    }
+
+                    if fix {
+                        let (applied, remaining) =
+                            validation::fix(file, &file_diagnostics, fix_dry_run)?;
+                        fixes_applied += applied;
+                        fixes_remaining += remaining;
+                    }
+
+                    diagnostics.append(&mut file_diagnostics);
+
+                    // With --fail-fast, stop looking at the remaining files as soon as one of
+                    // them fails validation.
+                    if fail_fast && file_failed {
+                        break;
+                    }
+                }
+                Err(error) => {
+                    if fail_fast {
+                        return Err(error);
+                    }
+
+                    failed_files += 1;
+                    log::error!("{}: {}", file, error);
+                }
+            }
+        }
+
+        validation::report(&diagnostics, output_format);
+
+        if fix {
+            log::info!(
+                "{} {} fix(es){}, {} issue(s) still need manual attention",
+                if fix_dry_run {
+                    "Would apply"
+                } else {
+                    "Applied"
+                },
+                fixes_applied,
+                if fix_dry_run { " (dry run)" } else { "" },
+                fixes_remaining,
+            );
+        }
+
+        if failed_files > 0 {
+            color_eyre::eyre::bail!(
+                "{} of {} files failed validation",
+                failed_files,
+                files.len()
+            );
         }
     }
 
@@ -137,3 +294,73 @@ fn process_module_type(
 
     modules_from_type.collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validation::Span;
+
+    #[test]
+    fn resolve_target_dir_prefers_cli_over_config() {
+        let cli = Some(String::from("from-cli"));
+        let config = Some(String::from("from-config"));
+
+        assert_eq!(
+            resolve_target_dir(cli, config, String::from(".")),
+            "from-cli"
+        );
+    }
+
+    #[test]
+    fn resolve_target_dir_falls_back_to_config_when_cli_is_absent() {
+        let config = Some(String::from("from-config"));
+
+        assert_eq!(
+            resolve_target_dir(None, config, String::from(".")),
+            "from-config"
+        );
+    }
+
+    #[test]
+    fn resolve_target_dir_falls_back_to_default_when_both_are_absent() {
+        assert_eq!(resolve_target_dir(None, None, String::from(".")), ".");
+    }
+
+    #[test]
+    fn resolve_bool_flag_negation_wins_even_over_a_true_config_value() {
+        assert!(!resolve_bool_flag(true, Some(true)));
+    }
+
+    #[test]
+    fn resolve_bool_flag_falls_back_to_config_when_not_negated() {
+        assert!(!resolve_bool_flag(false, Some(false)));
+        assert!(resolve_bool_flag(false, None));
+    }
+
+    fn diagnostic(level: Level, message: &str) -> Diagnostic {
+        Diagnostic {
+            level,
+            file: String::from("test.adoc"),
+            span: Span { line: 1, column: 1 },
+            message: message.to_string(),
+            suggestion: None,
+        }
+    }
+
+    #[test]
+    fn has_error_diagnostic_detects_errors_among_warnings() {
+        let diagnostics = vec![
+            diagnostic(Level::Warning, "a warning"),
+            diagnostic(Level::Error, "an error"),
+        ];
+
+        assert!(has_error_diagnostic(&diagnostics));
+    }
+
+    #[test]
+    fn has_error_diagnostic_is_false_with_only_warnings() {
+        let diagnostics = vec![diagnostic(Level::Warning, "a warning")];
+
+        assert!(!has_error_diagnostic(&diagnostics));
+    }
+}
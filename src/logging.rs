@@ -0,0 +1,19 @@
+use crate::Verbosity;
+use color_eyre::eyre::Result;
+
+/// Configure the `env_logger`-based logging system based on the requested verbosity.
+pub fn initialize_logger(verbosity: Verbosity) -> Result<()> {
+    let level = match verbosity {
+        Verbosity::Verbose => log::LevelFilter::Debug,
+        Verbosity::Default => log::LevelFilter::Info,
+        Verbosity::Quiet => log::LevelFilter::Warn,
+    };
+
+    env_logger::Builder::new()
+        .filter_level(level)
+        .format_timestamp(None)
+        .format_target(false)
+        .try_init()?;
+
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use crate::{templating, Options};
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModuleType {
+    Assembly,
+    Concept,
+    Procedure,
+    Reference,
+    Snippet,
+}
+
+impl ModuleType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Assembly => "assembly",
+            Self::Concept => "concept",
+            Self::Procedure => "procedure",
+            Self::Reference => "reference",
+            Self::Snippet => "snippet",
+        }
+    }
+}
+
+/// Everything needed to generate a single module, before the AsciiDoc body is rendered.
+pub struct Input<'a> {
+    module_type: ModuleType,
+    title: &'a str,
+    options: &'a Options,
+    includes: Option<Vec<String>>,
+}
+
+impl<'a> Input<'a> {
+    pub fn new(module_type: ModuleType, title: &'a str, options: &'a Options) -> Self {
+        Self {
+            module_type,
+            title,
+            options,
+            includes: None,
+        }
+    }
+
+    /// Attach include statements to be embedded in the generated body, turning it into a
+    /// populated assembly.
+    pub fn include(mut self, includes: Vec<String>) -> Self {
+        self.includes = Some(includes);
+        self
+    }
+}
+
+/// A single generated module or assembly, ready to be written out.
+#[derive(Debug, Clone)]
+pub struct Module {
+    pub module_type: ModuleType,
+    pub title: String,
+    pub id: String,
+    pub file_name: String,
+    pub include_statement: String,
+    pub text: String,
+}
+
+impl Module {
+    pub fn new(module_type: ModuleType, title: &str, options: &Options) -> Self {
+        Input::new(module_type, title, options).into()
+    }
+
+    pub fn file_path(&self, options: &Options) -> PathBuf {
+        PathBuf::from(&options.target_dir).join(&self.file_name)
+    }
+}
+
+impl From<Input<'_>> for Module {
+    fn from(input: Input<'_>) -> Self {
+        let id = templating::convert_title_to_id(input.module_type, input.title, input.options);
+        let file_name = format!("{}.adoc", id);
+        let include_statement = format!("include::{}[leveloffset=+1]", file_name);
+        let text = templating::render_body(
+            input.module_type,
+            input.title,
+            &id,
+            input.options,
+            input.includes.as_deref(),
+        );
+
+        Self {
+            module_type: input.module_type,
+            title: input.title.to_string(),
+            id,
+            file_name,
+            include_statement,
+            text,
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use crate::{ModuleType, Options};
+
+/// Lowercase `text` and replace every character that isn't alphanumeric with a `-`, so the
+/// result is always safe to use as an AsciiDoc ID and as a file name on any filesystem.
+fn slugify(text: &str) -> String {
+    text.to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '-' })
+        .collect()
+}
+
+/// Build the AsciiDoc ID for a module from its title, respecting the configured prefix rules.
+pub fn convert_title_to_id(module_type: ModuleType, title: &str, options: &Options) -> String {
+    let slug = slugify(title);
+
+    if options.prefixes {
+        // The prefix is free-form user input from the config file (see `Config::title_prefixes`)
+        // -- run it through the same transform as the title so a value like "Concept: " can't
+        // produce an invalid ID or an unquotable file name.
+        let prefix = options
+            .title_prefixes
+            .get(module_type.as_str())
+            .map(|prefix| slugify(prefix))
+            .unwrap_or_else(|| format!("{}_", module_type.as_str()));
+
+        format!("{}{}", prefix, slug)
+    } else {
+        slug
+    }
+}
+
+/// Render the AsciiDoc body for a module: the ID attribute and title, an optional intro comment,
+/// and then either the given include statements or an optional example paragraph.
+pub fn render_body(
+    module_type: ModuleType,
+    title: &str,
+    id: &str,
+    options: &Options,
+    includes: Option<&[String]>,
+) -> String {
+    let mut body = format!("[id=\"{}\"]\n= {}\n\n", id, title);
+
+    if options.comments {
+        body.push_str(&format!(
+            "// A short intro providing more context for the {} module.\n\n",
+            module_type.as_str()
+        ));
+    }
+
+    if let Some(includes) = includes {
+        for include in includes {
+            body.push_str(include);
+            body.push('\n');
+        }
+    } else if options.examples {
+        body.push_str("An example paragraph for this module.\n");
+    }
+
+    body
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Verbosity, WriteMode};
+    use std::collections::HashMap;
+
+    fn options_with_title_prefixes(title_prefixes: HashMap<String, String>) -> Options {
+        Options {
+            comments: true,
+            prefixes: true,
+            examples: true,
+            target_dir: String::from("."),
+            verbosity: Verbosity::Default,
+            title_prefixes,
+            write_mode: WriteMode::Overwrite,
+        }
+    }
+
+    #[test]
+    fn convert_title_to_id_sanitizes_an_unsafe_configured_prefix() {
+        let mut title_prefixes = HashMap::new();
+        title_prefixes.insert(String::from("concept"), String::from("Concept: "));
+        let options = options_with_title_prefixes(title_prefixes);
+
+        assert_eq!(
+            convert_title_to_id(ModuleType::Concept, "Hello there", &options),
+            "concept--hello-there"
+        );
+    }
+
+    #[test]
+    fn convert_title_to_id_falls_back_to_the_default_prefix() {
+        let options = options_with_title_prefixes(HashMap::new());
+
+        assert_eq!(
+            convert_title_to_id(ModuleType::Concept, "Hello there", &options),
+            "concept_hello-there"
+        );
+    }
+}
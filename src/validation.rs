@@ -0,0 +1,532 @@
+use crate::OutputFormat;
+use color_eyre::eyre::Result;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// How serious a [`Diagnostic`] is. Only `Error` should ever fail the overall validation run;
+/// `Warning` and `Note` are informational.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    Error,
+    Warning,
+    Note,
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            Self::Error => "error",
+            Self::Warning => "warning",
+            Self::Note => "note",
+        };
+
+        write!(f, "{}", text)
+    }
+}
+
+/// A 1-based line/column position in a source file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// A mechanical fix for a [`Diagnostic`]: replace the bytes in `start..end` with
+/// `replacement`. Offsets are byte offsets into the original file content.
+#[derive(Debug, Clone)]
+pub struct Suggestion {
+    pub start: usize,
+    pub end: usize,
+    pub replacement: String,
+}
+
+/// A single validation finding for a file, with enough information for both a human-readable
+/// report and machine-readable output (JSON, GitHub Actions annotations).
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub level: Level,
+    pub file: String,
+    pub span: Span,
+    pub message: String,
+    /// Set when `message` describes something `--fix` can repair mechanically.
+    pub suggestion: Option<Suggestion>,
+}
+
+impl Diagnostic {
+    fn new(level: Level, file: &str, span: Span, message: impl Into<String>) -> Self {
+        Self {
+            level,
+            file: file.to_string(),
+            span,
+            message: message.into(),
+            suggestion: None,
+        }
+    }
+
+    fn with_suggestion(mut self, suggestion: Suggestion) -> Self {
+        self.suggestion = Some(suggestion);
+        self
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}: {}: {}",
+            self.file, self.span.line, self.span.column, self.level, self.message
+        )
+    }
+}
+
+/// Guess the module type a file was generated as from its newdoc-style file name prefix (for
+/// example `concept_`, as produced by [`templating::convert_title_to_id`](crate::templating)),
+/// falling back to `concept` when the name doesn't follow the convention.
+fn guess_module_type(file_name: &str) -> (&'static str, &'static str) {
+    let base = Path::new(file_name)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(file_name);
+
+    for (id_prefix, content_type) in [
+        ("assembly_", "ASSEMBLY"),
+        ("concept_", "CONCEPT"),
+        ("procedure_", "PROCEDURE"),
+        ("reference_", "REFERENCE"),
+        ("snippet_", "SNIPPET"),
+    ] {
+        if base.starts_with(id_prefix) {
+            return (id_prefix, content_type);
+        }
+    }
+
+    ("concept_", "CONCEPT")
+}
+
+/// Validate a single AsciiDoc file, checking it against newdoc's authoring conventions.
+///
+/// Unlike the file I/O itself, which still returns an error on a hard read failure, every
+/// authoring issue that can be found is collected into the returned `Vec` rather than
+/// short-circuiting on the first one.
+pub fn validate(file_name: &str) -> Result<Vec<Diagnostic>> {
+    let content = fs::read_to_string(file_name)?;
+    let mut diagnostics = Vec::new();
+    let (id_prefix, content_type) = guess_module_type(file_name);
+
+    if !content.contains(":_content-type:") {
+        diagnostics.push(
+            Diagnostic::new(
+                Level::Error,
+                file_name,
+                Span { line: 1, column: 1 },
+                "missing the `:_content-type:` attribute",
+            )
+            .with_suggestion(Suggestion {
+                start: 0,
+                end: 0,
+                replacement: format!(":_content-type: {}\n\n", content_type),
+            }),
+        );
+    }
+
+    if let Some(attribute_offset) = content.find("[id=\"") {
+        let value_start = attribute_offset + "[id=\"".len();
+
+        if let Some(value_len) = content[value_start..].find('"') {
+            let id = &content[value_start..value_start + value_len];
+
+            if !id.starts_with(id_prefix) {
+                let line = content[..value_start].matches('\n').count() + 1;
+
+                diagnostics.push(
+                    Diagnostic::new(
+                        Level::Warning,
+                        file_name,
+                        Span { line, column: 1 },
+                        format!("module ID is missing the `{}` prefix", id_prefix),
+                    )
+                    .with_suggestion(Suggestion {
+                        start: value_start,
+                        end: value_start + value_len,
+                        replacement: format!("{}{}", id_prefix, id),
+                    }),
+                );
+            }
+        }
+    }
+
+    let mut offset = 0;
+
+    // `split_inclusive` keeps each line's terminator (`\n` or `\r\n`) so that `offset` stays a
+    // true byte offset whatever the line endings are.
+    for (index, line) in content.split_inclusive('\n').enumerate() {
+        if line.contains("include::") && !line.contains("leveloffset=") {
+            let mut diagnostic = Diagnostic::new(
+                Level::Warning,
+                file_name,
+                Span {
+                    line: index + 1,
+                    column: 1,
+                },
+                "include statement is missing a `leveloffset` attribute",
+            );
+
+            // The common case is an empty bracket, e.g. `include::module.adoc[]`, which can be
+            // mechanically repaired; anything more unusual is left for manual attention.
+            if let Some(position) = line.find("[]") {
+                diagnostic = diagnostic.with_suggestion(Suggestion {
+                    start: offset + position,
+                    end: offset + position + 2,
+                    replacement: "[leveloffset=+1]".to_string(),
+                });
+            }
+
+            diagnostics.push(diagnostic);
+        }
+
+        offset += line.len();
+    }
+
+    Ok(diagnostics)
+}
+
+/// Apply a file's [`Suggestion`]s to its content and report how many were actually applied.
+///
+/// Suggestions are sorted by descending start offset and applied in that order so that an
+/// earlier edit never invalidates the byte offsets of a later one; any suggestion that would
+/// overlap one already applied is skipped rather than corrupting the file.
+fn apply_suggestions(content: &str, suggestions: &[&Suggestion]) -> (String, usize) {
+    let mut ordered: Vec<&Suggestion> = suggestions.to_vec();
+    ordered.sort_by_key(|suggestion| std::cmp::Reverse(suggestion.start));
+
+    let mut result = content.to_string();
+    let mut applied = 0;
+    let mut last_applied_start = content.len();
+
+    for suggestion in ordered {
+        if suggestion.end > last_applied_start {
+            log::warn!("Skipping a suggestion that overlaps one already applied");
+            continue;
+        }
+
+        result.replace_range(suggestion.start..suggestion.end, &suggestion.replacement);
+        last_applied_start = suggestion.start;
+        applied += 1;
+    }
+
+    (result, applied)
+}
+
+/// Apply every fixable suggestion in `diagnostics` to `file_name`, or, if `dry_run` is set,
+/// print a unified diff of the proposed edits without touching the file. Returns the number of
+/// suggestions applied (or that would be applied) and the number of remaining diagnostics that
+/// still need manual attention.
+pub fn fix(file_name: &str, diagnostics: &[Diagnostic], dry_run: bool) -> Result<(usize, usize)> {
+    let fixable: Vec<&Suggestion> = diagnostics
+        .iter()
+        .filter_map(|diagnostic| diagnostic.suggestion.as_ref())
+        .collect();
+
+    if fixable.is_empty() {
+        return Ok((0, diagnostics.len()));
+    }
+
+    let content = fs::read_to_string(file_name)?;
+    let (fixed, applied) = apply_suggestions(&content, &fixable);
+    // A suggestion that overlapped one already applied is skipped by `apply_suggestions`, so
+    // the count of diagnostics still needing manual attention must be derived from `applied`
+    // rather than from `fixable.len()` up front, or a skipped-for-overlap suggestion would be
+    // counted in neither bucket.
+    let remaining = diagnostics.len() - applied;
+
+    if dry_run {
+        print_unified_diff(file_name, &content, &fixed);
+    } else {
+        fs::write(file_name, &fixed)?;
+    }
+
+    Ok((applied, remaining))
+}
+
+/// A single line in a line-level diff between two pieces of text.
+enum DiffOp<'a> {
+    Equal(&'a str),
+    Delete(&'a str),
+    Insert(&'a str),
+}
+
+/// Diff two sequences of lines, returning the edit script as a sequence of [`DiffOp`]s.
+///
+/// This is a plain textbook LCS diff (see e.g. the algorithm behind `diff`/`git diff`), which is
+/// more than fast enough for the single small AsciiDoc file `--fix` operates on at a time; it
+/// produces real `Insert`/`Delete` operations rather than assuming every edit lands on the same
+/// line number, so it stays correct even when a suggestion adds or removes whole lines.
+fn diff_lines<'a>(original: &[&'a str], fixed: &[&'a str]) -> Vec<DiffOp<'a>> {
+    let (n, m) = (original.len(), fixed.len());
+    let mut lcs_len = vec![vec![0usize; m + 1]; n + 1];
+
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs_len[i][j] = if original[i] == fixed[j] {
+                lcs_len[i + 1][j + 1] + 1
+            } else {
+                lcs_len[i + 1][j].max(lcs_len[i][j + 1])
+            };
+        }
+    }
+
+    let mut ops = Vec::new();
+    let (mut i, mut j) = (0, 0);
+
+    while i < n && j < m {
+        if original[i] == fixed[j] {
+            ops.push(DiffOp::Equal(original[i]));
+            i += 1;
+            j += 1;
+        } else if lcs_len[i + 1][j] >= lcs_len[i][j + 1] {
+            ops.push(DiffOp::Delete(original[i]));
+            i += 1;
+        } else {
+            ops.push(DiffOp::Insert(fixed[j]));
+            j += 1;
+        }
+    }
+
+    ops.extend(original[i..].iter().map(|line| DiffOp::Delete(line)));
+    ops.extend(fixed[j..].iter().map(|line| DiffOp::Insert(line)));
+
+    ops
+}
+
+/// How many lines of unchanged context to show around a hunk, matching the default used by the
+/// standard `diff -u`.
+const DIFF_CONTEXT_LINES: usize = 3;
+
+/// Print a real unified diff (`--- a/...` / `+++ b/...` / `@@ -l,s +l,s @@`) between a file's
+/// original and fixed content, so `--fix --diff` can be piped straight into `patch` or read the
+/// same way a `git diff` would be.
+fn print_unified_diff(file_name: &str, original: &str, fixed: &str) {
+    let original_lines: Vec<&str> = original.lines().collect();
+    let fixed_lines: Vec<&str> = fixed.lines().collect();
+    let ops = diff_lines(&original_lines, &fixed_lines);
+
+    let Some(first_change) = ops.iter().position(|op| !matches!(op, DiffOp::Equal(_))) else {
+        return;
+    };
+    let last_change = ops
+        .iter()
+        .rposition(|op| !matches!(op, DiffOp::Equal(_)))
+        .unwrap();
+
+    let start = first_change.saturating_sub(DIFF_CONTEXT_LINES);
+    let end = (last_change + DIFF_CONTEXT_LINES + 1).min(ops.len());
+
+    let original_start = 1 + ops[..start]
+        .iter()
+        .filter(|op| !matches!(op, DiffOp::Insert(_)))
+        .count();
+    let fixed_start = 1 + ops[..start]
+        .iter()
+        .filter(|op| !matches!(op, DiffOp::Delete(_)))
+        .count();
+    let original_len = ops[start..end]
+        .iter()
+        .filter(|op| !matches!(op, DiffOp::Insert(_)))
+        .count();
+    let fixed_len = ops[start..end]
+        .iter()
+        .filter(|op| !matches!(op, DiffOp::Delete(_)))
+        .count();
+
+    println!("--- a/{}", file_name);
+    println!("+++ b/{}", file_name);
+    println!(
+        "@@ -{},{} +{},{} @@",
+        original_start, original_len, fixed_start, fixed_len
+    );
+
+    for op in &ops[start..end] {
+        match op {
+            DiffOp::Equal(line) => println!(" {}", line),
+            DiffOp::Delete(line) => println!("-{}", line),
+            DiffOp::Insert(line) => println!("+{}", line),
+        }
+    }
+}
+
+/// Print a set of diagnostics in the requested output format.
+pub fn report(diagnostics: &[Diagnostic], format: OutputFormat) {
+    match format {
+        OutputFormat::Text => {
+            for diagnostic in diagnostics {
+                println!("{}", diagnostic);
+            }
+        }
+        OutputFormat::Json => match serde_json::to_string_pretty(&diagnostics_as_json(diagnostics))
+        {
+            Ok(json) => println!("{}", json),
+            Err(error) => log::error!("Failed to serialize diagnostics as JSON: {}", error),
+        },
+        OutputFormat::GithubActions => {
+            for diagnostic in diagnostics {
+                report_github_actions(diagnostic);
+            }
+        }
+    }
+}
+
+fn diagnostics_as_json(diagnostics: &[Diagnostic]) -> Vec<serde_json::Value> {
+    diagnostics
+        .iter()
+        .map(|diagnostic| {
+            serde_json::json!({
+                "level": diagnostic.level.to_string(),
+                "file": diagnostic.file,
+                "line": diagnostic.span.line,
+                "column": diagnostic.span.column,
+                "message": diagnostic.message,
+            })
+        })
+        .collect()
+}
+
+/// Percent-encode the characters that would otherwise corrupt a GitHub Actions workflow command
+/// if they appeared in its message (the part after `::`).
+fn escape_workflow_command_data(text: &str) -> String {
+    text.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Percent-encode the characters that would otherwise corrupt a GitHub Actions workflow command
+/// if they appeared in one of its `key=value` properties, on top of everything
+/// `escape_workflow_command_data` already escapes.
+fn escape_workflow_command_property(text: &str) -> String {
+    escape_workflow_command_data(text)
+        .replace(',', "%2C")
+        .replace(':', "%3A")
+}
+
+/// Emit a single diagnostic as a GitHub Actions workflow command, so it shows up as an inline
+/// annotation on the pull request diff.
+fn report_github_actions(diagnostic: &Diagnostic) {
+    let command = match diagnostic.level {
+        Level::Error => "error",
+        Level::Warning => "warning",
+        Level::Note => "notice",
+    };
+
+    println!(
+        "::{} file={},line={},col={}::{}",
+        command,
+        escape_workflow_command_property(&diagnostic.file),
+        diagnostic.span.line,
+        diagnostic.span.column,
+        escape_workflow_command_data(&diagnostic.message),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escape_workflow_command_data_escapes_percent_and_newlines() {
+        assert_eq!(
+            escape_workflow_command_data("100% done\r\nnext line"),
+            "100%25 done%0D%0Anext line"
+        );
+    }
+
+    #[test]
+    fn escape_workflow_command_property_also_escapes_commas_and_colons() {
+        assert_eq!(
+            escape_workflow_command_property("src/a,b:c.adoc"),
+            "src/a%2Cb%3Ac.adoc"
+        );
+    }
+
+    #[test]
+    fn diff_lines_handles_an_insertion_that_shifts_every_later_line() {
+        let original = vec!["body line one", "body line two"];
+        let fixed = vec!["header", "", "body line one", "body line two"];
+
+        let ops = diff_lines(&original, &fixed);
+        let rendered: Vec<(char, &str)> = ops
+            .iter()
+            .map(|op| match op {
+                DiffOp::Equal(line) => (' ', *line),
+                DiffOp::Delete(line) => ('-', *line),
+                DiffOp::Insert(line) => ('+', *line),
+            })
+            .collect();
+
+        assert_eq!(
+            rendered,
+            vec![
+                ('+', "header"),
+                ('+', ""),
+                (' ', "body line one"),
+                (' ', "body line two"),
+            ]
+        );
+    }
+
+    #[test]
+    fn apply_suggestions_replaces_from_the_end_so_offsets_stay_valid() {
+        let content = "one two three";
+        let first = Suggestion {
+            start: 0,
+            end: 3,
+            replacement: "1".to_string(),
+        };
+        let second = Suggestion {
+            start: 4,
+            end: 7,
+            replacement: "2".to_string(),
+        };
+
+        let (result, applied) = apply_suggestions(content, &[&first, &second]);
+
+        assert_eq!(result, "1 2 three");
+        assert_eq!(applied, 2);
+    }
+
+    #[test]
+    fn apply_suggestions_handles_a_zero_width_insertion_at_the_start() {
+        let content = "body";
+        let insertion = Suggestion {
+            start: 0,
+            end: 0,
+            replacement: "header\n".to_string(),
+        };
+
+        let (result, applied) = apply_suggestions(content, &[&insertion]);
+
+        assert_eq!(result, "header\nbody");
+        assert_eq!(applied, 1);
+    }
+
+    #[test]
+    fn apply_suggestions_skips_one_of_two_overlapping_suggestions() {
+        let content = "abcdef";
+        let first = Suggestion {
+            start: 0,
+            end: 4,
+            replacement: "X".to_string(),
+        };
+        let second = Suggestion {
+            start: 2,
+            end: 6,
+            replacement: "Y".to_string(),
+        };
+
+        let (result, applied) = apply_suggestions(content, &[&first, &second]);
+
+        // The later (larger-start) suggestion wins; the earlier one, which overlaps it, is
+        // skipped rather than corrupting the result.
+        assert_eq!(result, "abY");
+        assert_eq!(applied, 1);
+    }
+}
@@ -0,0 +1,168 @@
+use crate::{Module, Options, WriteMode};
+use color_eyre::eyre::{bail, Result};
+use std::fs;
+
+/// Produce every generated module according to the configured [`WriteMode`], so the caller
+/// never has to special-case "write to disk" versus "print" versus "check" itself.
+pub fn write_modules(modules: &[Module], options: &Options) -> Result<()> {
+    match options.write_mode {
+        WriteMode::Overwrite => {
+            for module in modules {
+                write_to_disk(module, options)?;
+            }
+        }
+        WriteMode::Stdout => {
+            for module in modules {
+                print_to_stdout(module, options);
+            }
+        }
+        WriteMode::Check => {
+            let mut failed = 0;
+
+            for module in modules {
+                if let Err(error) = check_module(module, options) {
+                    log::error!("{}", error);
+                    failed += 1;
+                }
+            }
+
+            if failed > 0 {
+                bail!(
+                    "{} of {} files would change or are missing",
+                    failed,
+                    modules.len()
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn write_to_disk(module: &Module, options: &Options) -> Result<()> {
+    let path = module.file_path(options);
+
+    log::info!(
+        "Writing the {} module to {:?}",
+        module.module_type.as_str(),
+        path
+    );
+    fs::write(path, &module.text)?;
+
+    Ok(())
+}
+
+/// Print a single module's generated body to the standard output, with a separator header
+/// identifying which target file it belongs to, so the output can be piped or inspected.
+fn print_to_stdout(module: &Module, options: &Options) {
+    println!("==== {} ====", module.file_path(options).display());
+    println!("{}", module.text);
+}
+
+fn check_module(module: &Module, options: &Options) -> Result<()> {
+    let path = module.file_path(options);
+
+    match fs::read_to_string(&path) {
+        Ok(existing) if existing == module.text => Ok(()),
+        Ok(_) => bail!("{:?} already exists and its content would change", path),
+        Err(_) => bail!("{:?} does not exist", path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ModuleType, Verbosity};
+    use std::collections::HashMap;
+    use std::path::{Path, PathBuf};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    /// Create a fresh, empty directory under the system temp directory, unique to this test run.
+    fn temp_dir(label: &str) -> PathBuf {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let dir = std::env::temp_dir().join(format!("newdoc-write-test-{}-{}", label, nanos));
+
+        fs::create_dir_all(&dir).unwrap();
+
+        dir
+    }
+
+    fn options_targeting(target_dir: &Path, write_mode: WriteMode) -> Options {
+        Options {
+            comments: true,
+            prefixes: true,
+            examples: true,
+            target_dir: target_dir.to_str().unwrap().to_string(),
+            verbosity: Verbosity::Default,
+            title_prefixes: HashMap::new(),
+            write_mode,
+        }
+    }
+
+    #[test]
+    fn write_modules_overwrite_writes_the_module_to_disk() {
+        let dir = temp_dir("overwrite");
+        let options = options_targeting(&dir, WriteMode::Overwrite);
+        let module = Module::new(ModuleType::Concept, "Hello there", &options);
+
+        write_modules(&[module.clone()], &options).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(module.file_path(&options)).unwrap(),
+            module.text
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn write_modules_check_passes_when_the_file_already_matches() {
+        let dir = temp_dir("check-match");
+        let options = options_targeting(&dir, WriteMode::Check);
+        let module = Module::new(ModuleType::Concept, "Hello there", &options);
+        fs::write(module.file_path(&options), &module.text).unwrap();
+
+        assert!(write_modules(&[module], &options).is_ok());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn write_modules_check_fails_when_the_file_is_missing() {
+        let dir = temp_dir("check-missing");
+        let options = options_targeting(&dir, WriteMode::Check);
+        let module = Module::new(ModuleType::Concept, "Hello there", &options);
+
+        assert!(write_modules(&[module], &options).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn write_modules_check_fails_when_the_file_would_change() {
+        let dir = temp_dir("check-would-change");
+        let options = options_targeting(&dir, WriteMode::Check);
+        let module = Module::new(ModuleType::Concept, "Hello there", &options);
+        fs::write(module.file_path(&options), "stale content").unwrap();
+
+        assert!(write_modules(&[module], &options).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn write_modules_stdout_does_not_touch_the_filesystem() {
+        let dir = temp_dir("stdout");
+        let options = options_targeting(&dir, WriteMode::Stdout);
+        let module = Module::new(ModuleType::Concept, "Hello there", &options);
+
+        write_modules(&[module.clone()], &options).unwrap();
+
+        assert!(!module.file_path(&options).exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}